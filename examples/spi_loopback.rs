@@ -0,0 +1,38 @@
+#![no_std]
+#![no_main]
+
+use k210_hal::pac::Peripherals;
+use k210_hal::prelude::*;
+use k210_hal::spi::{SpiConfig, SpiExt, Tmod};
+use panic_halt as _;
+use riscv_rt::entry;
+
+/// Loopback self-test: send a known buffer through SPI0 with the controller's
+/// internal TX->RX loopback enabled (or MOSI tied to MISO) and assert it comes
+/// back unchanged.
+#[entry]
+fn main() -> ! {
+    let p = Peripherals::take().unwrap();
+
+    let mut sysctl = p.SYSCTL.constrain();
+    let clocks = k210_hal::clock::Clocks::new();
+
+    let mut spi = p.SPI0.constrain(&mut sysctl.apb2);
+    spi.configure(
+        &SpiConfig::default()
+            .tmod(Tmod::TRANS_RECV)
+            .loopback(true),
+    );
+    spi.set_clk_rate(1.mhz().into(), &clocks);
+    spi.set_slave_select(Some(0));
+
+    let expected: [u8; 8] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+    let mut buffer = expected;
+    spi.transfer_bytes(&mut buffer).unwrap();
+
+    assert_eq!(buffer, expected);
+
+    loop {
+        continue;
+    }
+}
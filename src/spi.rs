@@ -3,7 +3,13 @@ use crate::dmac::{Dmac, DmacChannel, Inc, Msize, TrWidth};
 use crate::pac::{self, SPI0, SPI1};
 use crate::sysctl::{self, DmaSelect, APB2};
 use crate::time::Hertz;
+use core::cell::UnsafeCell;
+use core::future::Future;
 use core::ops::Deref;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+use riscv::interrupt;
 
 /** Borrow frame format from pac */
 pub use crate::pac::spi0::ctrlr0::FRAME_FORMAT_A as FrameFormat;
@@ -43,12 +49,14 @@ impl<SPI: Spi01> SpiExt for SPI {
         Spi {
             spi: self,
             slave_select: None,
+            loopback: false,
         }
     }
 }
 pub struct Spi<SPI> {
     spi: SPI,
     slave_select: Option<u8>,
+    loopback: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,34 +64,365 @@ pub enum SpiError {
     NoSlaveSelect,
     NoClockRateSpecified,
     WillCauseMemoryError,
+    InvalidDataLength,
+    InvalidInstructionLength,
+    InvalidAddressLength,
+    InvalidWaitCycles,
+    Overrun,
+    RxFifoFull,
+    TxUnderflow,
+}
+
+/// Validated SPI configuration consumed by [`Spi::configure`].
+///
+/// Start from [`SpiConfig::default`] (mode 0, 8-bit words, standard frame,
+/// full-duplex TX/RX) and override what you need through the builder methods.
+/// The length/range fields are private: the only way to set them is through
+/// the builder methods below, so a bad length is rejected as an [`SpiError`]
+/// when `SpiConfig` is built, rather than being silently misencoded (or
+/// `panic!`ing, as `configure` used to) later on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpiConfig {
+    pub work_mode: WorkMode,
+    pub frame_format: FrameFormat,
+    data_length: u8,
+    pub endian: u32,
+    instruction_length: u8,
+    address_length: u8,
+    wait_cycles: u8,
+    pub aitm: Aitm,
+    pub tmod: Tmod,
+    pub loopback: bool,
+}
+
+impl Default for SpiConfig {
+    fn default() -> Self {
+        SpiConfig {
+            work_mode: WorkMode::MODE0,
+            frame_format: FrameFormat::STANDARD,
+            data_length: 8,
+            endian: 0,
+            instruction_length: 0,
+            address_length: 0,
+            wait_cycles: 0,
+            aitm: Aitm::STANDARD,
+            tmod: Tmod::TRANS_RECV,
+            loopback: false,
+        }
+    }
+}
+
+impl SpiConfig {
+    /// Clock polarity/phase work mode.
+    pub fn work_mode(mut self, work_mode: WorkMode) -> Self {
+        self.work_mode = work_mode;
+        self
+    }
+
+    /// Standard, dual, quad or octal frame format.
+    pub fn frame_format(mut self, frame_format: FrameFormat) -> Self {
+        self.frame_format = frame_format;
+        self
+    }
+
+    /// Bit endianness register value.
+    pub fn endian(mut self, endian: u32) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// How instruction and address bits map onto the data lines.
+    pub fn aitm(mut self, aitm: Aitm) -> Self {
+        self.aitm = aitm;
+        self
+    }
+
+    /// Transfer mode (transmit, receive or both).
+    pub fn tmod(mut self, tmod: Tmod) -> Self {
+        self.tmod = tmod;
+        self
+    }
+
+    /// Enable the internal TX->RX loopback self-test.
+    pub fn loopback(mut self, loopback: bool) -> Self {
+        self.loopback = loopback;
+        self
+    }
+
+    /// Word length in bits; must be in `4..=32`.
+    pub fn data_length(mut self, data_length: u8) -> Result<Self, SpiError> {
+        if data_length < 4 || data_length > 32 {
+            return Err(SpiError::InvalidDataLength);
+        }
+        self.data_length = data_length;
+        Ok(self)
+    }
+
+    /// Instruction length in bits; must be one of `0`, `4`, `8`, `16`.
+    pub fn instruction_length(mut self, instruction_length: u8) -> Result<Self, SpiError> {
+        match instruction_length {
+            0 | 4 | 8 | 16 => {
+                self.instruction_length = instruction_length;
+                Ok(self)
+            }
+            _ => Err(SpiError::InvalidInstructionLength),
+        }
+    }
+
+    /// Address length in bits; must be a multiple of 4 and at most 60.
+    pub fn address_length(mut self, address_length: u8) -> Result<Self, SpiError> {
+        if address_length % 4 != 0 || address_length > 60 {
+            return Err(SpiError::InvalidAddressLength);
+        }
+        self.address_length = address_length;
+        Ok(self)
+    }
+
+    /// Dummy wait cycles between address and data; must fit in 5 bits.
+    pub fn wait_cycles(mut self, wait_cycles: u8) -> Result<Self, SpiError> {
+        if wait_cycles >= (1 << 5) {
+            return Err(SpiError::InvalidWaitCycles);
+        }
+        self.wait_cycles = wait_cycles;
+        Ok(self)
+    }
+}
+
+/// Per-channel transfer-complete flags, set by the DMAC interrupt handler and
+/// observed by [`SpiDmaFuture::poll`]. There are six DMAC channels on the K210.
+static DMA_DONE: [AtomicBool; 6] = [
+    AtomicBool::new(false),
+    AtomicBool::new(false),
+    AtomicBool::new(false),
+    AtomicBool::new(false),
+    AtomicBool::new(false),
+    AtomicBool::new(false),
+];
+
+/// Per-channel waker slot. The executor's task waker is stashed here while an
+/// [`SpiDmaFuture`] is pending; the DMAC ISR wakes it on transfer completion.
+struct WakerSlot(UnsafeCell<Option<Waker>>);
+
+// SAFETY: the cell is only ever touched inside `interrupt::free`, so accesses
+// are mutually exclusive with the DMAC ISR that also touches it.
+unsafe impl Sync for WakerSlot {}
+
+impl WakerSlot {
+    const fn new() -> Self {
+        WakerSlot(UnsafeCell::new(None))
+    }
+}
+
+static DMA_WAKERS: [WakerSlot; 6] = [
+    WakerSlot::new(),
+    WakerSlot::new(),
+    WakerSlot::new(),
+    WakerSlot::new(),
+    WakerSlot::new(),
+    WakerSlot::new(),
+];
+
+/// Call this from the application's DMAC interrupt handler, once per channel
+/// whose transfer-complete interrupt has fired (after clearing the hardware
+/// flag). It records completion and wakes the task awaiting that channel.
+pub fn on_dmac_transfer_complete(channel: DmacChannel) {
+    let idx = channel as usize;
+    DMA_DONE[idx].store(true, Ordering::Release);
+    interrupt::free(|_| unsafe {
+        if let Some(waker) = (*DMA_WAKERS[idx].0.get()).take() {
+            waker.wake();
+        }
+    });
+}
+
+fn register_dma_waker(idx: usize, waker: &Waker) {
+    interrupt::free(|_| unsafe {
+        let slot = &mut *DMA_WAKERS[idx].0.get();
+        match slot {
+            Some(existing) if existing.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+    });
+}
+
+/// Inspect the controller's error status and map it to an [`SpiError`].
+///
+/// Reads the raw interrupt status (`risr`) and the status register (`sr`): a
+/// receive-FIFO overflow is reported as [`SpiError::Overrun`] after its
+/// sticky flag is cleared through the `rxoicr` clear register, a raised
+/// receive-FIFO-full interrupt as [`SpiError::RxFifoFull`], and a
+/// transmission error as [`SpiError::TxUnderflow`]. Shared by
+/// [`Spi::check_status`] and [`SpiDmaFuture::poll`], which only hold a
+/// reference to the raw register block.
+fn check_spi_status<SPI: Spi01>(spi: &SPI) -> Result<(), SpiError> {
+    let risr = unsafe { spi.risr.read().bits() };
+    let sr = unsafe { spi.sr.read().bits() };
+    if risr & (1 << 3) != 0 {
+        // Clear the sticky receive-overflow flag by reading its clear register.
+        unsafe {
+            spi.rxoicr.read();
+        }
+        return Err(SpiError::Overrun);
+    }
+    if risr & (1 << 4) != 0 {
+        return Err(SpiError::RxFifoFull);
+    }
+    if sr & (1 << 5) != 0 {
+        return Err(SpiError::TxUnderflow);
+    }
+    Ok(())
+}
+
+/// Future resolving once a DMA-driven SPI transfer started by
+/// [`Spi::send_data_dma_async`] or [`Spi::recv_data_dma_async`] has completed.
+///
+/// Polling runs the `ser`/`ssienr` teardown once the channel reports done, so
+/// the controller is left idle and deselected exactly as the blocking paths
+/// leave it. The `'a` lifetime is shared with the DMA buffer borrowed by the
+/// `recv`/`send` call that created this future (see their signatures), so the
+/// borrow checker keeps that buffer alive and untouched by safe code for as
+/// long as the DMAC might still be reading or writing it. If the future is
+/// dropped before it resolves, [`Drop`] disables the channel so the DMAC lets
+/// go of the buffer immediately instead of continuing to run against memory
+/// that may no longer be valid.
+///
+/// Holding `dmac: &'a mut Dmac` for the future's whole lifetime, rather than
+/// only for the duration of the call that started the transfer, means no
+/// other channel on the same [`Dmac`] can be driven while this future is
+/// pending. That's the price of being able to reach into the DMAC and cancel
+/// this channel from [`Drop`]; splitting per-channel access out of `Dmac`
+/// would remove the restriction but isn't something this driver does today.
+pub struct SpiDmaFuture<'a, SPI: Spi01> {
+    spi: &'a SPI,
+    dmac: &'a mut Dmac,
+    channel: DmacChannel,
+    torn_down: bool,
+}
+
+impl<SPI: Spi01> Future for SpiDmaFuture<'_, SPI> {
+    type Output = Result<(), SpiError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let idx = self.channel as usize;
+        // Register before checking the flag: if the ISR fires between the
+        // check and the registration, `DMA_DONE` is already set by the time
+        // we load it below, so we fall through to the ready path instead of
+        // registering a waker for a transfer that will never wake it again.
+        register_dma_waker(idx, cx.waker());
+        if !DMA_DONE[idx].load(Ordering::Acquire) {
+            return Poll::Pending;
+        }
+        // The shift register can still be draining for a few cycles after
+        // DMA-complete; rather than spin here and block the executor, check
+        // once and ask to be polled again so other tasks get a turn.
+        let idle = unsafe { (self.spi.sr.read().bits() & 0x05) == 0x04 };
+        if !idle {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let status = check_spi_status(self.spi);
+        unsafe {
+            self.spi.ser.write(|w| w.bits(0x00));
+            self.spi.ssienr.write(|w| w.bits(0x00));
+        }
+        self.get_mut().torn_down = true;
+        Poll::Ready(status)
+    }
+}
+
+impl<SPI: Spi01> Drop for SpiDmaFuture<'_, SPI> {
+    fn drop(&mut self) {
+        if self.torn_down {
+            // `poll` already reached `Poll::Ready` and tore the controller
+            // down itself; nothing left to cancel. Note this is distinct
+            // from `DMA_DONE`, which the ISR can set while `poll` is still
+            // waiting out the shift-register-idle check above, i.e. before
+            // teardown has actually run.
+            return;
+        }
+        let idx = self.channel as usize;
+        // Dropped while still in flight (task cancellation, a panic
+        // unwinding past the `.await`, ...): stop the DMAC channel and
+        // deselect the controller so neither keeps touching the buffer
+        // this future borrowed once it's gone.
+        self.dmac.disable_channel(self.channel);
+        unsafe {
+            self.spi.ser.write(|w| w.bits(0x00));
+            self.spi.ssienr.write(|w| w.bits(0x00));
+        }
+        interrupt::free(|_| unsafe {
+            *DMA_WAKERS[idx].0.get() = None;
+        });
+    }
+}
+
+/// `SRL` bit in `ctrlr0`: routes the transmit shift register back into the
+/// receive shift register for the internal loopback self-test.
+const CTRLR0_SRL: u32 = 1 << 11;
+
+macro_rules! impl_transfer {
+    ($name:ident, $t:ty, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Each word is pushed into the TX FIFO and the matching RX word is
+        /// drained in lockstep (`txflr`/`rxflr`), overwriting the buffer in
+        /// place, with `Tmod::TRANS_RECV`. Honours [`set_loopback`](Self::set_loopback).
+        pub fn $name(&mut self, words: &mut [$t]) -> Result<(), SpiError> {
+            if self.slave_select.is_none() {
+                return Err(SpiError::NoSlaveSelect);
+            }
+            unsafe {
+                self.spi.ssienr.write(|w| w.bits(0x00));
+                self.spi.ctrlr0.modify(|r, w| {
+                    let bits = if self.loopback {
+                        r.bits() | CTRLR0_SRL
+                    } else {
+                        r.bits() & !CTRLR0_SRL
+                    };
+                    w.bits(bits).tmod().variant(Tmod::TRANS_RECV)
+                });
+                self.spi.ssienr.write(|w| w.bits(0x01));
+                self.spi
+                    .ser
+                    .write(|w| w.bits(1 << self.slave_select.unwrap()));
+
+                for word in words.iter_mut() {
+                    while 32 - self.spi.txflr.read().bits() == 0 {
+                        // wait until shift register is available
+                    }
+                    self.spi.dr[0].write(|w| w.bits(*word as u32));
+                    while self.spi.rxflr.read().bits() == 0 {
+                        // IDLE
+                    }
+                    self.check_status()?;
+                    *word = self.spi.dr[0].read().bits() as $t;
+                }
+
+                while (self.spi.sr.read().bits() & 0x05) != 0x04 {
+                    // IDLE
+                }
+
+                self.spi.ser.write(|w| w.bits(0x00));
+                self.spi.ssienr.write(|w| w.bits(0x00));
+            }
+            Ok(())
+        }
+    };
 }
 
 impl<SPI: Spi01> Spi<SPI> {
-    /// Configure the SPI before transferring.
-    pub fn configure(
-        &mut self,
-        work_mode: WorkMode,
-        frame_format: FrameFormat,
-        data_length: u8,
-        endian: u32,
-        instruction_length: u8,
-        address_length: u8,
-        wait_cycles: u8,
-        instruction_address_trans_mode: Aitm,
-        tmod: Tmod,
-    ) {
-        assert!(data_length >= 4 && data_length <= 32);
-        assert!(wait_cycles < (1 << 5));
-        let inst_l: u8 = match instruction_length {
-            0 => 0,
+    /// Configure the SPI before transferring from a validated [`SpiConfig`].
+    pub fn configure(&mut self, cfg: &SpiConfig) {
+        // All ranges were validated when `cfg` was built, so the encodings
+        // below cannot fall through.
+        let inst_l: u8 = match cfg.instruction_length {
             4 => 1,
             8 => 2,
             16 => 3,
-            _ => panic!("unhandled instruction length"),
+            _ => 0,
         };
-
-        assert!(address_length % 4 == 0 && address_length <= 60);
-        let addr_l: u8 = address_length / 4;
+        let addr_l: u8 = cfg.address_length / 4;
+        self.loopback = cfg.loopback;
 
         unsafe {
             self.spi.imr.write(|w| w.bits(0x00));
@@ -94,25 +433,25 @@ impl<SPI: Spi01> Spi<SPI> {
             self.spi.ssienr.write(|w| w.bits(0x00));
             self.spi.ctrlr0.write(|w| {
                 w.work_mode()
-                    .variant(work_mode)
+                    .variant(cfg.work_mode)
                     .tmod()
-                    .variant(tmod)
+                    .variant(cfg.tmod)
                     .frame_format()
-                    .variant(frame_format)
+                    .variant(cfg.frame_format)
                     .data_length()
-                    .bits(data_length - 1)
+                    .bits(cfg.data_length - 1)
             });
             self.spi.spi_ctrlr0.write(|w| {
                 w.aitm()
-                    .variant(instruction_address_trans_mode)
+                    .variant(cfg.aitm)
                     .addr_length()
                     .bits(addr_l)
                     .inst_length()
                     .bits(inst_l)
                     .wait_cycles()
-                    .bits(wait_cycles)
+                    .bits(cfg.wait_cycles)
             });
-            self.spi.endian.write(|w| w.bits(endian));
+            self.spi.endian.write(|w| w.bits(cfg.endian));
         }
     }
 
@@ -137,6 +476,70 @@ impl<SPI: Spi01> Spi<SPI> {
         self.slave_select = ss;
     }
 
+    /// Enable or disable internal loopback, where the controller feeds its
+    /// transmit shift register straight back into the receive shift register
+    /// (the DW-SSI `SRL` bit). With loopback off the same self-test can be run
+    /// by tying `MOSI` to `MISO` externally. Takes effect on the next
+    /// [`transfer`](Self::transfer).
+    pub fn set_loopback(&mut self, loopback: bool) {
+        self.loopback = loopback;
+    }
+
+    /// DMAC transfer width matching a buffer element of type `X`, checked
+    /// against the controller's configured `data_length`.
+    ///
+    /// Picking the width from `size_of::<X>()` keeps the DMAC stride in step
+    /// with the buffer: a `[u8]` is packed one byte per element instead of one
+    /// word, and a `[u16]` is not zero-padded out to 32 bits. Returns
+    /// [`SpiError::InvalidDataLength`] rather than panicking when the buffer's
+    /// element size doesn't match how the controller is framing the data
+    /// (e.g. a `[u8]` buffer with `data_length` left at 32).
+    fn dma_tr_width<X>(&self) -> Result<TrWidth, SpiError> {
+        let data_length = unsafe { self.spi.ctrlr0.read().data_length().bits() } as usize + 1;
+        let bytes = core::mem::size_of::<X>();
+        if (data_length + 7) / 8 != bytes {
+            return Err(SpiError::InvalidDataLength);
+        }
+        Ok(match bytes {
+            1 => TrWidth::WIDTH_8,
+            2 => TrWidth::WIDTH_16,
+            _ => TrWidth::WIDTH_32,
+        })
+    }
+
+    /// DMAC burst size matching a buffer element of type `X`, alongside
+    /// [`dma_tr_width`](Self::dma_tr_width): byte buffers move one element per
+    /// burst so a short, unaligned tail isn't over-read, while 16/32-bit
+    /// buffers burst four elements at a time.
+    fn dma_msize<X>(&self) -> Msize {
+        match core::mem::size_of::<X>() {
+            1 => Msize::LENGTH_1,
+            _ => Msize::LENGTH_4,
+        }
+    }
+
+    /// See [`check_spi_status`]. Called before each word transfer completes
+    /// so stale FIFO data is never returned after an error.
+    fn check_status(&self) -> Result<(), SpiError> {
+        check_spi_status(&self.spi)
+    }
+
+    impl_transfer!(
+        transfer_bytes,
+        u8,
+        "Full-duplex transfer of 8-bit words, reading while writing."
+    );
+    impl_transfer!(
+        transfer_halfwords,
+        u16,
+        "Full-duplex transfer of 16-bit words, reading while writing."
+    );
+    impl_transfer!(
+        transfer,
+        u32,
+        "Full-duplex transfer of 32-bit words, reading while writing."
+    );
+
     /// Untested, might not work
     pub fn recv_data_dma<X: Into<u32> + Copy>(
         &self,
@@ -177,8 +580,8 @@ impl<SPI: Spi01> Spi<SPI> {
                 rx.as_ptr() as u64,
                 Inc::NOCHANGE,
                 Inc::INCREMENT,
-                TrWidth::WIDTH_32,
-                Msize::LENGTH_1,
+                self.dma_tr_width::<X>().map_err(nb::Error::Other)?,
+                self.dma_msize::<X>(),
                 rx.len() as u32,
             );
             self.spi.dr[0].write(|w| w.bits(0xffffffff));
@@ -187,6 +590,12 @@ impl<SPI: Spi01> Spi<SPI> {
                 .write(|w| w.bits(1 << self.slave_select.unwrap()));
             dmac.wait_done(channel);
 
+            while (self.spi.sr.read().bits() & 0x05) != 0x04 {
+                // IDLE
+            }
+
+            self.check_status().map_err(nb::Error::Other)?;
+
             self.spi.ser.write(|w| w.bits(0x00));
             self.spi.ssienr.write(|w| w.bits(0x00));
 
@@ -196,11 +605,11 @@ impl<SPI: Spi01> Spi<SPI> {
 
     /// Using direct memory access to transfer data from source address to SPI.
     /// (TODO: Move this into an isolate `FullDuplex` implementation which returns `DmaTransfer`.)
-    pub fn send_data_dma(
+    pub fn send_data_dma<X: Into<u32> + Copy>(
         &mut self,
         dmac: &mut Dmac,
         channel: DmacChannel,
-        tx: &[u32],
+        tx: &[X],
     ) -> nb::Result<(), SpiError> {
         unsafe {
             if self.slave_select.is_none() {
@@ -224,8 +633,8 @@ impl<SPI: Spi01> Spi<SPI> {
                 self.spi.dr.as_ptr() as u64,
                 Inc::INCREMENT,
                 Inc::NOCHANGE,
-                TrWidth::WIDTH_32,
-                Msize::LENGTH_4,
+                self.dma_tr_width::<X>().map_err(nb::Error::Other)?,
+                self.dma_msize::<X>(),
                 tx.len() as u32,
             );
 
@@ -241,12 +650,253 @@ impl<SPI: Spi01> Spi<SPI> {
                 // IDLE
             }
 
+            self.check_status().map_err(nb::Error::Other)?;
+
             self.spi.ser.write(|w| w.bits(0x00));
             self.spi.ssienr.write(|w| w.bits(0x00));
 
             Ok(())
         }
     }
+
+    /// Start a DMA receive and return a [`SpiDmaFuture`] that can be `.await`ed
+    /// instead of busy-waiting on `dmac.wait_done`. The channel's
+    /// transfer-complete interrupt must be routed to an ISR that calls
+    /// [`on_dmac_transfer_complete`] for this to make progress.
+    pub fn recv_data_dma_async<'a, X: Into<u32> + Copy>(
+        &'a self,
+        dmac: &'a mut Dmac,
+        channel: DmacChannel,
+        rx: &'a mut [X],
+    ) -> nb::Result<SpiDmaFuture<'a, SPI>, SpiError> {
+        if self.slave_select.is_none() {
+            return Err(nb::Error::Other(SpiError::NoSlaveSelect));
+        }
+
+        if match SPI::NUMBER {
+            SpiNumber::Spi0 => !sysctl::clk_en_peri().read().spi0_clk_en().bit(),
+            SpiNumber::Spi1 => !sysctl::clk_en_peri().read().spi1_clk_en().bit(),
+        } {
+            return Err(nb::Error::Other(SpiError::NoClockRateSpecified));
+        }
+
+        DMA_DONE[channel as usize].store(false, Ordering::Release);
+
+        unsafe {
+            self.spi.ctrlr1.write(|w| w.bits(rx.len() as u32 - 1));
+            self.spi.ssienr.write(|w| w.bits(0x01));
+            self.spi.dmacr.write(|w| w.bits(0x3)); /*enable dma receive */
+
+            sysctl::set_dma_sel(
+                channel,
+                match SPI::NUMBER {
+                    SpiNumber::Spi0 => DmaSelect::SSI0_RX_REQ,
+                    SpiNumber::Spi1 => DmaSelect::SSI1_RX_REQ,
+                },
+            );
+
+            dmac.set_single_mode(
+                channel,
+                self.spi.dr.as_ptr() as u64,
+                rx.as_ptr() as u64,
+                Inc::NOCHANGE,
+                Inc::INCREMENT,
+                self.dma_tr_width::<X>().map_err(nb::Error::Other)?,
+                self.dma_msize::<X>(),
+                rx.len() as u32,
+            );
+            dmac.enable_interrupt(channel);
+            self.spi.dr[0].write(|w| w.bits(0xffffffff));
+            self.spi
+                .ser
+                .write(|w| w.bits(1 << self.slave_select.unwrap()));
+        }
+
+        Ok(SpiDmaFuture { spi: &self.spi, dmac, channel, torn_down: false })
+    }
+
+    /// Start a DMA transmit and return a [`SpiDmaFuture`] that completes once the
+    /// transfer-complete interrupt has fired, letting the executor run other
+    /// tasks while e.g. a framebuffer is flushed. Requires the channel's
+    /// interrupt to drive [`on_dmac_transfer_complete`].
+    pub fn send_data_dma_async<'a, X: Into<u32> + Copy>(
+        &'a self,
+        dmac: &'a mut Dmac,
+        channel: DmacChannel,
+        tx: &'a [X],
+    ) -> nb::Result<SpiDmaFuture<'a, SPI>, SpiError> {
+        if self.slave_select.is_none() {
+            return Err(nb::Error::Other(SpiError::NoSlaveSelect));
+        }
+
+        DMA_DONE[channel as usize].store(false, Ordering::Release);
+
+        unsafe {
+            self.spi.dmacr.write(|w| w.bits(0x2));
+            self.spi.ssienr.write(|w| w.bits(0x1));
+
+            sysctl::set_dma_sel(
+                channel,
+                match SPI::NUMBER {
+                    SpiNumber::Spi0 => DmaSelect::SSI0_TX_REQ,
+                    SpiNumber::Spi1 => DmaSelect::SSI1_TX_REQ,
+                },
+            );
+
+            dmac.set_single_mode(
+                channel,
+                tx.as_ptr() as u64,
+                self.spi.dr.as_ptr() as u64,
+                Inc::INCREMENT,
+                Inc::NOCHANGE,
+                self.dma_tr_width::<X>().map_err(nb::Error::Other)?,
+                self.dma_msize::<X>(),
+                tx.len() as u32,
+            );
+            dmac.enable_interrupt(channel);
+
+            self.spi
+                .ser
+                .write(|w| w.bits(1 << self.slave_select.unwrap()));
+        }
+
+        Ok(SpiDmaFuture { spi: &self.spi, dmac, channel, torn_down: false })
+    }
+
+    /// Drive the instruction/address engine to read `buf` over DMA.
+    ///
+    /// The controller must already be configured with the matching
+    /// `inst_length`/`addr_length`/`wait_cycles` and a receive `Tmod`; this
+    /// pushes the opcode (and optional address) into the engine, then streams
+    /// the response through the DMA receive path. Used by [`SpiFlash`].
+    fn read_engine(
+        &self,
+        dmac: &mut Dmac,
+        channel: DmacChannel,
+        command: u32,
+        addr: Option<u32>,
+        buf: &mut [u8],
+    ) -> nb::Result<(), SpiError> {
+        if self.slave_select.is_none() {
+            return Err(nb::Error::Other(SpiError::NoSlaveSelect));
+        }
+        unsafe {
+            self.spi.ctrlr1.write(|w| w.bits(buf.len() as u32 - 1));
+            self.spi.ssienr.write(|w| w.bits(0x01));
+            self.spi.dmacr.write(|w| w.bits(0x3));
+
+            sysctl::set_dma_sel(
+                channel,
+                match SPI::NUMBER {
+                    SpiNumber::Spi0 => DmaSelect::SSI0_RX_REQ,
+                    SpiNumber::Spi1 => DmaSelect::SSI1_RX_REQ,
+                },
+            );
+
+            dmac.set_single_mode(
+                channel,
+                self.spi.dr.as_ptr() as u64,
+                buf.as_ptr() as u64,
+                Inc::NOCHANGE,
+                Inc::INCREMENT,
+                TrWidth::WIDTH_8,
+                Msize::LENGTH_1,
+                buf.len() as u32,
+            );
+
+            self.spi.dr[0].write(|w| w.bits(command));
+            if let Some(addr) = addr {
+                self.spi.dr[0].write(|w| w.bits(addr));
+            }
+            self.spi
+                .ser
+                .write(|w| w.bits(1 << self.slave_select.unwrap()));
+            dmac.wait_done(channel);
+
+            self.check_status().map_err(nb::Error::Other)?;
+
+            self.spi.ser.write(|w| w.bits(0x00));
+            self.spi.ssienr.write(|w| w.bits(0x00));
+        }
+        Ok(())
+    }
+
+    /// Programmed-I/O variant of [`read_engine`](Self::read_engine) for short
+    /// responses (IDs, status registers) that don't warrant a DMA channel.
+    fn read_engine_pio(
+        &self,
+        command: u32,
+        addr: Option<u32>,
+        buf: &mut [u8],
+    ) -> nb::Result<(), SpiError> {
+        if self.slave_select.is_none() {
+            return Err(nb::Error::Other(SpiError::NoSlaveSelect));
+        }
+        unsafe {
+            self.spi.ctrlr1.write(|w| w.bits(buf.len() as u32 - 1));
+            self.spi.ssienr.write(|w| w.bits(0x01));
+            self.spi.dr[0].write(|w| w.bits(command));
+            if let Some(addr) = addr {
+                self.spi.dr[0].write(|w| w.bits(addr));
+            }
+            self.spi
+                .ser
+                .write(|w| w.bits(1 << self.slave_select.unwrap()));
+
+            for slot in buf.iter_mut() {
+                while self.spi.rxflr.read().bits() == 0 {
+                    // IDLE
+                }
+                self.check_status().map_err(nb::Error::Other)?;
+                *slot = self.spi.dr[0].read().bits() as u8;
+            }
+
+            self.spi.ser.write(|w| w.bits(0x00));
+            self.spi.ssienr.write(|w| w.bits(0x00));
+        }
+        Ok(())
+    }
+
+    /// Send an opcode, optional 24-bit address and data bytes through the
+    /// instruction/address engine in a transmit `Tmod`. Used by [`SpiFlash`]
+    /// for erase and program commands.
+    fn write_engine(
+        &self,
+        command: u32,
+        addr: Option<u32>,
+        data: &[u8],
+    ) -> nb::Result<(), SpiError> {
+        if self.slave_select.is_none() {
+            return Err(nb::Error::Other(SpiError::NoSlaveSelect));
+        }
+        unsafe {
+            self.spi.ssienr.write(|w| w.bits(0x01));
+            self.spi.dr[0].write(|w| w.bits(command));
+            if let Some(addr) = addr {
+                self.spi.dr[0].write(|w| w.bits(addr));
+            }
+            self.spi
+                .ser
+                .write(|w| w.bits(1 << self.slave_select.unwrap()));
+
+            let mut fifo_len = 0;
+            for &byte in data {
+                while fifo_len == 0 {
+                    fifo_len = 32 - self.spi.txflr.read().bits();
+                }
+                self.spi.dr[0].write(|w| w.bits(byte as u32));
+                fifo_len -= 1;
+            }
+
+            while (self.spi.sr.read().bits() & 0x05) != 0x04 {
+                // IDLE
+            }
+
+            self.spi.ser.write(|w| w.bits(0x00));
+            self.spi.ssienr.write(|w| w.bits(0x00));
+        }
+        Ok(())
+    }
 }
 
 macro_rules! impl_simple_full_duplex {
@@ -281,6 +931,8 @@ macro_rules! impl_simple_full_duplex {
                         // IDLE
                     }
 
+                    self.check_status().map_err(nb::Error::Other)?;
+
                     rx = self.spi.dr[0].read().bits().max(0).min(<$t>::MAX as u32) as $t;
 
                     self.spi.ser.write(|w| w.bits(0x00));
@@ -318,6 +970,8 @@ macro_rules! impl_simple_full_duplex {
                         // IDLE
                     }
 
+                    self.check_status().map_err(nb::Error::Other)?;
+
                     self.spi.ser.write(|w| w.bits(0x00));
                     self.spi.ssienr.write(|w| w.bits(0x00));
 
@@ -375,3 +1029,172 @@ impl<SPI: Spi01, X: Into<u32> + Copy> FullDuplex<&[X]> for Spi<SPI> {
         Ok(())
     }
 }
+
+/// Standard SPI-NOR flash commands used by [`SpiFlash`].
+mod flash_cmd {
+    pub const READ: u8 = 0x03;
+    pub const DUAL_OUTPUT_READ: u8 = 0x3b;
+    pub const QUAD_OUTPUT_READ: u8 = 0x6b;
+    pub const READ_ID: u8 = 0x9f;
+    pub const READ_STATUS: u8 = 0x05;
+    pub const WRITE_ENABLE: u8 = 0x06;
+    pub const SECTOR_ERASE: u8 = 0x20;
+    pub const PAGE_PROGRAM: u8 = 0x02;
+}
+
+/// Driver for standard SPI-NOR flash built on top of a configured [`Spi`].
+///
+/// It drives the controller's instruction/address/wait-cycle engine (the
+/// `inst_length`, `addr_length`, `wait_cycles` and `aitm` fields already wired
+/// up in [`Spi::configure`]) so fast-read protocols run in hardware. The
+/// frame format selects between standard, dual- and quad-output reads; the
+/// response is streamed through the existing DMA receive path.
+pub struct SpiFlash<SPI> {
+    spi: Spi<SPI>,
+    frame_format: FrameFormat,
+}
+
+impl<SPI: Spi01> SpiFlash<SPI> {
+    /// Wrap a [`Spi`] whose clock rate and slave select have already been set.
+    /// Reads default to the standard single-line protocol; call
+    /// [`with_frame_format`](Self::with_frame_format) for dual/quad reads.
+    pub fn new(spi: Spi<SPI>) -> Self {
+        SpiFlash {
+            spi,
+            frame_format: FrameFormat::STANDARD,
+        }
+    }
+
+    /// Select the frame format used by [`read`](Self::read): `STANDARD`,
+    /// `DUAL` or `QUAD`. Erase and program always run in standard mode.
+    pub fn with_frame_format(mut self, frame_format: FrameFormat) -> Self {
+        self.frame_format = frame_format;
+        self
+    }
+
+    /// Release the wrapped [`Spi`].
+    pub fn free(self) -> Spi<SPI> {
+        self.spi
+    }
+
+    /// The fast-read opcode and dummy wait cycles for the configured frame
+    /// format.
+    fn read_command(&self) -> (u8, u8) {
+        match self.frame_format {
+            FrameFormat::DUAL => (flash_cmd::DUAL_OUTPUT_READ, 8),
+            FrameFormat::QUAD => (flash_cmd::QUAD_OUTPUT_READ, 8),
+            _ => (flash_cmd::READ, 0),
+        }
+    }
+
+    /// Read `buf.len()` bytes starting at flash `addr`, streamed over DMA.
+    ///
+    /// The controller sends the read opcode (8-bit instruction) and the 24-bit
+    /// address, waits the dummy cycles required by the frame format, then
+    /// clocks the data in. `DUAL_OUTPUT_READ`/`QUAD_OUTPUT_READ` (`0x3B`/
+    /// `0x6B`) are the single-line-address variants, so the instruction and
+    /// address always go out standard (single-line) via [`Aitm::STANDARD`]
+    /// and only the data phase follows `frame_format`'s line count.
+    /// [`Aitm::AS_FRAME_FORMAT`] is for the dual/quad-I/O opcodes (`0xBB`/
+    /// `0xEB`), which this driver doesn't issue yet.
+    pub fn read(
+        &mut self,
+        dmac: &mut Dmac,
+        channel: DmacChannel,
+        addr: u32,
+        buf: &mut [u8],
+    ) -> nb::Result<(), SpiError> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let (command, wait_cycles) = self.read_command();
+        let cfg = SpiConfig::default()
+            .frame_format(self.frame_format)
+            .instruction_length(8)
+            .map_err(nb::Error::Other)?
+            .address_length(24)
+            .map_err(nb::Error::Other)?
+            .wait_cycles(wait_cycles)
+            .map_err(nb::Error::Other)?
+            .aitm(Aitm::STANDARD)
+            .tmod(Tmod::RECV);
+        self.spi.configure(&cfg);
+        self.spi
+            .read_engine(dmac, channel, command as u32, Some(addr), buf)
+    }
+
+    /// Read the 3-byte JEDEC manufacturer/device ID (opcode `0x9F`).
+    pub fn read_id(&mut self) -> nb::Result<[u8; 3], SpiError> {
+        let cfg = SpiConfig::default()
+            .instruction_length(8)
+            .map_err(nb::Error::Other)?
+            .tmod(Tmod::RECV);
+        self.spi.configure(&cfg);
+        let mut id = [0u8; 3];
+        self.spi
+            .read_engine_pio(flash_cmd::READ_ID as u32, None, &mut id)?;
+        Ok(id)
+    }
+
+    /// Erase the 4 KiB sector containing `addr` (opcode `0x20`).
+    pub fn sector_erase(&mut self, addr: u32) -> nb::Result<(), SpiError> {
+        self.write_enable()?;
+        self.command_with_addr(flash_cmd::SECTOR_ERASE, addr)?;
+        self.wait_while_busy()
+    }
+
+    /// Program up to a page of `data` at `addr` (opcode `0x02`). The caller is
+    /// responsible for staying within a single 256-byte page.
+    pub fn page_program(&mut self, addr: u32, data: &[u8]) -> nb::Result<(), SpiError> {
+        self.write_enable()?;
+        let cfg = SpiConfig::default()
+            .instruction_length(8)
+            .map_err(nb::Error::Other)?
+            .address_length(24)
+            .map_err(nb::Error::Other)?
+            .tmod(Tmod::TRANS);
+        self.spi.configure(&cfg);
+        self.spi
+            .write_engine(flash_cmd::PAGE_PROGRAM as u32, Some(addr), data)?;
+        self.wait_while_busy()
+    }
+
+    /// Issue the write-enable latch command (opcode `0x06`).
+    fn write_enable(&mut self) -> nb::Result<(), SpiError> {
+        let cfg = SpiConfig::default()
+            .instruction_length(8)
+            .map_err(nb::Error::Other)?
+            .tmod(Tmod::TRANS);
+        self.spi.configure(&cfg);
+        self.spi.write_engine(flash_cmd::WRITE_ENABLE as u32, None, &[])
+    }
+
+    /// Send a standard-mode command carrying a 24-bit address and no data.
+    fn command_with_addr(&mut self, command: u8, addr: u32) -> nb::Result<(), SpiError> {
+        let cfg = SpiConfig::default()
+            .instruction_length(8)
+            .map_err(nb::Error::Other)?
+            .address_length(24)
+            .map_err(nb::Error::Other)?
+            .tmod(Tmod::TRANS);
+        self.spi.configure(&cfg);
+        self.spi.write_engine(command as u32, Some(addr), &[])
+    }
+
+    /// Poll the status register until the write-in-progress bit clears.
+    fn wait_while_busy(&mut self) -> nb::Result<(), SpiError> {
+        let cfg = SpiConfig::default()
+            .instruction_length(8)
+            .map_err(nb::Error::Other)?
+            .tmod(Tmod::RECV);
+        self.spi.configure(&cfg);
+        loop {
+            let mut status = [0u8; 1];
+            self.spi
+                .read_engine_pio(flash_cmd::READ_STATUS as u32, None, &mut status)?;
+            if status[0] & 0x01 == 0 {
+                return Ok(());
+            }
+        }
+    }
+}